@@ -0,0 +1,201 @@
+use clap::ValueEnum;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CacheFile {
+    pub(crate) domain: String,
+    pub(crate) salt: String,
+    pub(crate) iterations: u32,
+    pub(crate) wordlist_size: usize,
+    pub(crate) hashes: HashMap<String, String>,
+}
+
+/// Compression applied to cache files on disk.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum CompressionMode {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl CompressionMode {
+    fn extension(self) -> &'static str {
+        match self {
+            CompressionMode::None => "",
+            CompressionMode::Gzip => ".gz",
+            CompressionMode::Zstd => ".zst",
+        }
+    }
+}
+
+/// Generate cache filename from salt and iterations (the uncompressed,
+/// logical cache key — the file on disk may carry a `.gz`/`.zst` suffix).
+pub(crate) fn get_cache_filename(salt: &str, iterations: u32) -> String {
+    let cache_key = format!("{salt}_{iterations}");
+    let mut hasher = md5::Context::new();
+    hasher.consume(cache_key.as_bytes());
+    let hash = format!("{:x}", hasher.compute());
+    format!("nsec3_{hash}.json")
+}
+
+/// Write a cache file, streaming the serialized JSON directly into the
+/// (optionally compressed) output file rather than building the whole
+/// JSON string in memory first.
+pub(crate) fn write_cache_file(
+    cache: &CacheFile,
+    output_dir: &Path,
+    cache_filename: &str,
+    compress: CompressionMode,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let output_path = output_dir.join(format!("{cache_filename}{}", compress.extension()));
+    let file = File::create(&output_path)?;
+
+    match compress {
+        CompressionMode::None => {
+            let writer = BufWriter::new(file);
+            serde_json::to_writer_pretty(writer, cache)?;
+        }
+        CompressionMode::Gzip => {
+            let encoder = GzEncoder::new(file, flate2::Compression::default());
+            let writer = BufWriter::new(encoder);
+            serde_json::to_writer(writer, cache)?;
+        }
+        CompressionMode::Zstd => {
+            let encoder = zstd::Encoder::new(file, 0)?.auto_finish();
+            let writer = BufWriter::new(encoder);
+            serde_json::to_writer(writer, cache)?;
+        }
+    }
+
+    Ok(output_path)
+}
+
+/// Locate the cache file matching `cache_filename` in `output_dir`, checking
+/// the uncompressed name and the `.gz`/`.zst` variants written by
+/// [`write_cache_file`].
+fn resolve_cache_path(output_dir: &Path, cache_filename: &str) -> Option<PathBuf> {
+    for compress in [
+        CompressionMode::None,
+        CompressionMode::Gzip,
+        CompressionMode::Zstd,
+    ] {
+        let candidate = output_dir.join(format!("{cache_filename}{}", compress.extension()));
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+/// Load a cache file, transparently decompressing based on its extension.
+pub(crate) fn load_cache_file(
+    output_dir: &Path,
+    cache_filename: &str,
+) -> Result<CacheFile, Box<dyn std::error::Error>> {
+    let path = resolve_cache_path(output_dir, cache_filename).ok_or_else(|| {
+        format!(
+            "no cache file found for {} in {} (looked for .json, .json.gz, .json.zst)",
+            cache_filename,
+            output_dir.display()
+        )
+    })?;
+
+    let file = File::open(&path)?;
+    let cache = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => serde_json::from_reader(BufReader::new(GzDecoder::new(file)))?,
+        Some("zst") => serde_json::from_reader(BufReader::new(zstd::Decoder::new(file)?))?,
+        _ => serde_json::from_reader(BufReader::new(file))?,
+    };
+
+    Ok(cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_DIR_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_output_dir() -> PathBuf {
+        let id = TEST_DIR_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir =
+            std::env::temp_dir().join(format!("nsecgen_cache_test_{}_{}", std::process::id(), id));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sample_cache() -> CacheFile {
+        CacheFile {
+            domain: "example.com".to_string(),
+            salt: "AABBCCDD".to_string(),
+            iterations: 5,
+            wordlist_size: 2,
+            hashes: HashMap::from([
+                ("hash1".to_string(), "www.example.com".to_string()),
+                ("hash2".to_string(), "api.example.com".to_string()),
+            ]),
+        }
+    }
+
+    fn assert_round_trips(compress: CompressionMode) {
+        let dir = temp_output_dir();
+        let cache_filename = get_cache_filename("AABBCCDD", 5);
+        let cache = sample_cache();
+
+        write_cache_file(&cache, &dir, &cache_filename, compress).unwrap();
+        let loaded = load_cache_file(&dir, &cache_filename).unwrap();
+
+        assert_eq!(loaded.domain, cache.domain);
+        assert_eq!(loaded.salt, cache.salt);
+        assert_eq!(loaded.iterations, cache.iterations);
+        assert_eq!(loaded.hashes, cache.hashes);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn round_trips_uncompressed() {
+        assert_round_trips(CompressionMode::None);
+    }
+
+    #[test]
+    fn round_trips_gzip() {
+        assert_round_trips(CompressionMode::Gzip);
+    }
+
+    #[test]
+    fn round_trips_zstd() {
+        assert_round_trips(CompressionMode::Zstd);
+    }
+
+    #[test]
+    fn resolve_cache_path_prefers_uncompressed_when_multiple_exist() {
+        let dir = temp_output_dir();
+        let cache_filename = get_cache_filename("", 0);
+        let cache = sample_cache();
+
+        write_cache_file(&cache, &dir, &cache_filename, CompressionMode::Zstd).unwrap();
+        write_cache_file(&cache, &dir, &cache_filename, CompressionMode::Gzip).unwrap();
+        write_cache_file(&cache, &dir, &cache_filename, CompressionMode::None).unwrap();
+
+        let path = resolve_cache_path(&dir, &cache_filename).unwrap();
+        assert_eq!(path, dir.join(&cache_filename));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_cache_path_returns_none_when_missing() {
+        let dir = temp_output_dir();
+        assert!(resolve_cache_path(&dir, "nsec3_missing.json").is_none());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,15 +1,23 @@
 use clap::{Parser, Subcommand};
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
+mod cache;
+mod mutations;
+mod prefixtree;
+mod profiles;
+mod zonewalk;
+
+use cache::{get_cache_filename, CacheFile, CompressionMode};
+use mutations::{MutationConfig, MutationRule};
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -39,6 +47,20 @@ struct Args {
     /// Number of threads (default: CPU cores)
     #[arg(short, long, global = true)]
     threads: Option<usize>,
+
+    /// Compress cache files on write (none, gzip, zstd)
+    #[arg(long, global = true, default_value = "none")]
+    compress: CompressionMode,
+
+    /// Expand the wordlist with these mutation rules before hashing
+    /// (comma-separated: numeric, env, separators, concat)
+    #[arg(long, global = true, value_delimiter = ',')]
+    mutations: Vec<MutationRule>,
+
+    /// TOML file overriding the numeric suffixes / env tags the mutation
+    /// rules draw from
+    #[arg(long, global = true)]
+    mutation_rules: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -70,16 +92,19 @@ enum Commands {
         /// Number of threads (default: CPU cores)
         #[arg(short, long)]
         threads: Option<usize>,
-    },
-}
 
-#[derive(Serialize, Deserialize)]
-struct CacheFile {
-    domain: String,
-    salt: String,
-    iterations: u32,
-    wordlist_size: usize,
-    hashes: HashMap<String, String>,
+        /// TOML or JSON file defining custom NSEC3 configurations to
+        /// generate instead of the built-in common ones
+        #[arg(short, long)]
+        profiles: Option<PathBuf>,
+    },
+    /// Reverse-resolve captured NSEC3 hashes back to names using a cache
+    ZoneWalk {
+        /// File of captured NSEC3 data: raw base32 owner hashes (one per
+        /// line) or full `dig`-style NSEC3 RR text
+        #[arg(short, long)]
+        records: PathBuf,
+    },
 }
 
 /// Calculate NSEC3 hash for a fully-qualified domain name
@@ -118,15 +143,6 @@ fn load_wordlist(path: &PathBuf) -> std::io::Result<Vec<String>> {
         .collect())
 }
 
-/// Generate cache filename from salt and iterations
-fn get_cache_filename(salt: &str, iterations: u32) -> String {
-    let cache_key = format!("{salt}_{iterations}");
-    let mut hasher = md5::Context::new();
-    hasher.consume(cache_key.as_bytes());
-    let hash = format!("{:x}", hasher.compute());
-    format!("nsec3_{hash}.json")
-}
-
 /// Download wordlist from URL and save to file
 fn download_wordlist(
     url: &str,
@@ -208,6 +224,53 @@ fn download_wordlists(output_dir: &PathBuf, size: &str) -> Result<(), Box<dyn st
     Ok(())
 }
 
+/// Merge a single (hash, fqdn) pair into a partial result map, logging
+/// rather than silently overwriting when two distinct FQDNs collide on the
+/// same NSEC3 hash.
+fn merge_hash(
+    mut map: HashMap<String, String>,
+    (hash, fqdn): (String, String),
+) -> HashMap<String, String> {
+    if let Some(existing) = map.insert(hash.clone(), fqdn.clone()) {
+        if existing != fqdn {
+            eprintln!(
+                "⚠️  NSEC3 hash collision: \"{}\" and \"{}\" both hash to {}",
+                existing, fqdn, hash
+            );
+        }
+    }
+    map
+}
+
+/// Compute `{subdomain}.{domain}` NSEC3 hashes in parallel and collect them
+/// into a single map. Each rayon thread folds its own partial map and the
+/// partials are merged with `reduce`, so there is no shared lock on the hot
+/// path. `progress`, when given, is driven by an `AtomicU64` counter rather
+/// than locking the progress bar per hash.
+fn compute_hashes(
+    subdomains: &[String],
+    domain: &str,
+    salt_bytes: &[u8],
+    iterations: u32,
+    progress: Option<(&ProgressBar, &AtomicU64)>,
+) -> HashMap<String, String> {
+    subdomains
+        .par_iter()
+        .map(|subdomain| {
+            let fqdn = format!("{}.{}", subdomain, domain);
+            let hash = calculate_nsec3_hash(&fqdn, salt_bytes, iterations);
+
+            if let Some((pb, counter)) = progress {
+                let done = counter.fetch_add(1, Ordering::Relaxed) + 1;
+                pb.set_position(done);
+            }
+
+            (hash, fqdn)
+        })
+        .fold(HashMap::new, merge_hash)
+        .reduce(HashMap::new, |a, b| b.into_iter().fold(a, merge_hash))
+}
+
 /// Generate hash for a single configuration
 fn generate_hash_for_config(
     domain: &str,
@@ -215,9 +278,20 @@ fn generate_hash_for_config(
     salt: &str,
     iterations: u32,
     output_dir: &PathBuf,
+    compress: CompressionMode,
+    mutations: &MutationConfig,
 ) -> Result<String, Box<dyn std::error::Error>> {
     // Load wordlist
-    let subdomains = load_wordlist(wordlist_path)?;
+    let base_subdomains = load_wordlist(wordlist_path)?;
+    let subdomains = mutations::expand(&base_subdomains, mutations);
+    if !mutations.is_empty() {
+        println!(
+            "   Mutations: {} -> {} entries ({:.1}x)",
+            base_subdomains.len(),
+            subdomains.len(),
+            subdomains.len() as f64 / base_subdomains.len().max(1) as f64
+        );
+    }
 
     // Parse salt from hex
     let salt_bytes = if salt.is_empty() {
@@ -226,33 +300,24 @@ fn generate_hash_for_config(
         hex::decode(salt).unwrap_or_else(|_| salt.as_bytes().to_vec())
     };
 
-    // Thread-safe hashmap for results
-    let hashes: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(HashMap::new()));
-
-    // Parallel processing with rayon
-    subdomains.par_iter().for_each(|subdomain| {
-        let fqdn = format!("{}.{}", subdomain, domain);
-        let hash = calculate_nsec3_hash(&fqdn, &salt_bytes, iterations);
-        hashes.lock().unwrap().insert(hash, fqdn);
-    });
+    // Parallel processing with rayon, collected lock-free
+    let hashes = compute_hashes(&subdomains, domain, &salt_bytes, iterations, None);
 
     // Create output directory
     fs::create_dir_all(output_dir)?;
 
     // Generate cache file
     let cache_filename = get_cache_filename(salt, iterations);
-    let output_path = output_dir.join(&cache_filename);
 
     let cache = CacheFile {
         domain: domain.to_string(),
         salt: salt.to_string(),
         iterations,
         wordlist_size: subdomains.len(),
-        hashes: hashes.lock().unwrap().clone(),
+        hashes,
     };
 
-    let json = serde_json::to_string_pretty(&cache)?;
-    fs::write(&output_path, json)?;
+    let output_path = cache::write_cache_file(&cache, output_dir, &cache_filename, compress)?;
 
     let file_size = fs::metadata(&output_path)?.len();
     Ok(format!(
@@ -268,6 +333,9 @@ fn generate_common_configs(
     wordlist_path: &PathBuf,
     output_dir: &PathBuf,
     threads: Option<usize>,
+    compress: CompressionMode,
+    profiles_path: Option<PathBuf>,
+    mutations: &MutationConfig,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Set thread pool size
     if let Some(threads) = threads {
@@ -281,18 +349,14 @@ fn generate_common_configs(
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!();
 
-    // Common configurations based on real-world statistics
-    // Format: (name, salt, iterations)
-    let configs = vec![
-        ("No salt, no iterations (30% of NSEC3 domains)", "", 0),
-        ("Google Cloud DNS", "DEADBEEF", 5),
-        ("AWS Route53", "CAFEBABE", 10),
-        ("Cloudflare minimal", "00", 0),
-        ("Light security", "AABBCCDD", 3),
-        ("Medium security", "12345678", 5),
-        ("High security", "FEDCBA98", 10),
-        ("Very high security", "FFFFFFFF", 15),
-    ];
+    let configs = match &profiles_path {
+        Some(path) => {
+            println!("   Using profiles: {}", path.display());
+            println!();
+            profiles::load_profiles(path)?
+        }
+        None => profiles::default_configs(),
+    };
 
     let total = configs.len();
     let start_time = Instant::now();
@@ -303,7 +367,15 @@ fn generate_common_configs(
         println!("   Iterations: {}", iterations);
 
         let config_start = Instant::now();
-        match generate_hash_for_config(domain, wordlist_path, salt, *iterations, output_dir) {
+        match generate_hash_for_config(
+            domain,
+            wordlist_path,
+            salt,
+            *iterations,
+            output_dir,
+            compress,
+            mutations,
+        ) {
             Ok(output_info) => {
                 println!("   ✓ Generated: {}", output_info);
                 println!("   Time: {:.2}s", config_start.elapsed().as_secs_f64());
@@ -344,6 +416,9 @@ fn generate_common_configs(
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
+    let mutation_config =
+        MutationConfig::load(args.mutations.clone(), args.mutation_rules.as_ref())?;
+
     // Handle subcommands
     if let Some(command) = args.command {
         match command {
@@ -355,8 +430,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 wordlist,
                 output,
                 threads,
+                profiles,
             } => {
-                return generate_common_configs(&domain, &wordlist, &output, threads);
+                return generate_common_configs(
+                    &domain,
+                    &wordlist,
+                    &output,
+                    threads,
+                    args.compress,
+                    profiles,
+                    &mutation_config,
+                );
+            }
+            Commands::ZoneWalk { records } => {
+                return zonewalk::run(&records, &args.salt, args.iterations, &args.output);
             }
         }
     }
@@ -396,12 +483,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     print!("📖 Loading wordlist... ");
     std::io::stdout().flush()?;
     let start = Instant::now();
-    let subdomains = load_wordlist(&wordlist)?;
+    let base_subdomains = load_wordlist(&wordlist)?;
+    let base_count = base_subdomains.len();
+    let subdomains = mutations::expand(&base_subdomains, &mutation_config);
     println!(
         "✓ {} subdomains loaded ({:.2}s)",
-        subdomains.len(),
+        base_count,
         start.elapsed().as_secs_f64()
     );
+    if !mutation_config.is_empty() {
+        println!(
+            "🧬 Mutations expanded wordlist to {} entries ({:.1}x)",
+            subdomains.len(),
+            subdomains.len() as f64 / base_count.max(1) as f64
+        );
+    }
 
     // Parse salt from hex
     let salt_bytes = if args.salt.is_empty() {
@@ -425,25 +521,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("⚡ Computing NSEC3 hashes...");
     let start = Instant::now();
 
-    // Thread-safe hashmap for results
-    let hashes: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(HashMap::new()));
-
-    // Parallel processing with rayon
-    subdomains.par_iter().for_each(|subdomain| {
-        let fqdn = format!("{}.{}", subdomain, domain);
-        let hash = calculate_nsec3_hash(&fqdn, &salt_bytes, args.iterations);
-
-        // Store result
-        hashes.lock().unwrap().insert(hash, fqdn);
-
-        // Update progress
-        pb.inc(1);
-    });
+    // Parallel processing with rayon, collected lock-free; the progress bar
+    // is driven by a shared counter instead of a mutex on the result map.
+    let progress_counter = AtomicU64::new(0);
+    let hashes = compute_hashes(
+        &subdomains,
+        &domain,
+        &salt_bytes,
+        args.iterations,
+        Some((&pb, &progress_counter)),
+    );
 
     pb.finish_with_message("Done!");
 
     let elapsed = start.elapsed();
-    let hashes_computed = hashes.lock().unwrap().len();
+    let hashes_computed = hashes.len();
     let hashes_per_sec = hashes_computed as f64 / elapsed.as_secs_f64();
 
     println!("\n✅ Hash computation complete!");
@@ -456,21 +548,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Generate cache file
     let cache_filename = get_cache_filename(&args.salt, args.iterations);
-    let output_path = args.output.join(&cache_filename);
 
     println!("\n💾 Saving cache file...");
-    println!("   Output: {}", output_path.display());
 
     let cache = CacheFile {
         domain: domain.clone(),
         salt: args.salt.clone(),
         iterations: args.iterations,
         wordlist_size: subdomains.len(),
-        hashes: hashes.lock().unwrap().clone(),
+        hashes,
     };
 
-    let json = serde_json::to_string_pretty(&cache)?;
-    fs::write(&output_path, json)?;
+    let output_path =
+        cache::write_cache_file(&cache, &args.output, &cache_filename, args.compress)?;
+    println!("   Output: {}", output_path.display());
 
     let file_size = fs::metadata(&output_path)?.len();
     println!("   Size: {:.2} MB", file_size as f64 / 1_048_576.0);
@@ -509,4 +600,38 @@ mod tests {
         let hash = calculate_nsec3_hash("example.com", &[], 10);
         assert_eq!(hash.len(), 32);
     }
+
+    #[test]
+    fn merge_hash_detects_collision_and_keeps_last_value() {
+        let map = HashMap::new();
+        let map = merge_hash(map, ("deadbeef".to_string(), "a.example.com".to_string()));
+        let map = merge_hash(map, ("deadbeef".to_string(), "b.example.com".to_string()));
+
+        // Two distinct FQDNs collided on the same hash: the map still has
+        // exactly one entry (the collision is logged, not silently dropped
+        // or duplicated) and it holds the most recently computed FQDN.
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("deadbeef"), Some(&"b.example.com".to_string()));
+    }
+
+    #[test]
+    fn merge_hash_keeps_distinct_entries_without_collision() {
+        let map = HashMap::new();
+        let map = merge_hash(map, ("hash1".to_string(), "a.example.com".to_string()));
+        let map = merge_hash(map, ("hash2".to_string(), "b.example.com".to_string()));
+
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn compute_hashes_collects_every_subdomain_via_fold_and_reduce() {
+        let subdomains = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let hashes = compute_hashes(&subdomains, "example.com", &[], 0, None);
+
+        assert_eq!(hashes.len(), subdomains.len());
+        for subdomain in &subdomains {
+            let fqdn = format!("{subdomain}.example.com");
+            assert!(hashes.values().any(|v| v == &fqdn));
+        }
+    }
 }
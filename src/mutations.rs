@@ -0,0 +1,264 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// A mutation rule that expands a base wordlist into name variants before
+/// hashing, so zone-walk coverage isn't limited to exact wordlist entries.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MutationRule {
+    /// Numeric prefixes/suffixes: www -> www1, www01, 2www
+    Numeric,
+    /// Environment tags: www -> www-dev, www.staging
+    Env,
+    /// Separator swaps: api.v1 -> api-v1 and vice versa
+    Separators,
+    /// Pairwise concatenation of two wordlist entries
+    Concat,
+}
+
+#[derive(Deserialize, Default)]
+struct MutationRulesFile {
+    /// `None` means "field omitted, use the built-in defaults"; `Some(vec![])`
+    /// means the user explicitly asked for no suffixes and must be honored.
+    #[serde(default)]
+    numeric_suffixes: Option<Vec<String>>,
+    #[serde(default)]
+    env_tags: Option<Vec<String>>,
+}
+
+const DEFAULT_NUMERIC_SUFFIXES: &[&str] = &["1", "01", "2", "02", "3"];
+const DEFAULT_ENV_TAGS: &[&str] = &["dev", "staging", "prod"];
+
+/// Resolved mutation settings: which rules are enabled, and the tag/suffix
+/// lists they draw from (built-in defaults, or overridden by a rule file).
+pub(crate) struct MutationConfig {
+    rules: Vec<MutationRule>,
+    numeric_suffixes: Vec<String>,
+    env_tags: Vec<String>,
+}
+
+impl MutationConfig {
+    pub(crate) fn load(
+        rules: Vec<MutationRule>,
+        rule_file: Option<&PathBuf>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let defaults = MutationRulesFile::default();
+        let parsed = match rule_file {
+            Some(path) => {
+                let data = std::fs::read_to_string(path)?;
+                toml::from_str(&data)?
+            }
+            None => defaults,
+        };
+
+        let numeric_suffixes = parsed.numeric_suffixes.unwrap_or_else(|| {
+            DEFAULT_NUMERIC_SUFFIXES
+                .iter()
+                .map(|s| s.to_string())
+                .collect()
+        });
+        let env_tags = parsed
+            .env_tags
+            .unwrap_or_else(|| DEFAULT_ENV_TAGS.iter().map(|s| s.to_string()).collect());
+
+        Ok(Self {
+            rules,
+            numeric_suffixes,
+            env_tags,
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Expand a base wordlist into mutated variants per the enabled rules,
+/// deduplicating the result before it reaches the hashing loop. With no
+/// rules enabled the wordlist is returned unchanged, duplicates included.
+pub(crate) fn expand(words: &[String], config: &MutationConfig) -> Vec<String> {
+    if config.is_empty() {
+        return words.to_vec();
+    }
+
+    let mut expanded: HashSet<String> = words.iter().cloned().collect();
+
+    for word in words {
+        if config.rules.contains(&MutationRule::Numeric) {
+            for suffix in &config.numeric_suffixes {
+                expanded.insert(format!("{word}{suffix}"));
+                expanded.insert(format!("{suffix}{word}"));
+            }
+        }
+
+        if config.rules.contains(&MutationRule::Env) {
+            for tag in &config.env_tags {
+                expanded.insert(format!("{word}-{tag}"));
+                expanded.insert(format!("{word}.{tag}"));
+            }
+        }
+
+        if config.rules.contains(&MutationRule::Separators) {
+            if word.contains('.') {
+                expanded.insert(word.replace('.', "-"));
+            }
+            if word.contains('-') {
+                expanded.insert(word.replace('-', "."));
+            }
+        }
+    }
+
+    // Pairwise concatenation is quadratic in the base wordlist size, so it's
+    // opt-in and applied once on the original words, not the already-expanded set.
+    if config.rules.contains(&MutationRule::Concat) {
+        for a in words {
+            for b in words {
+                if a != b {
+                    expanded.insert(format!("{a}{b}"));
+                }
+            }
+        }
+    }
+
+    expanded.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_FILE_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_rule_file(contents: &str) -> PathBuf {
+        let id = TEST_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "nsecgen_mutations_test_{}_{}.toml",
+            std::process::id(),
+            id
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_without_rule_file_uses_built_in_defaults() {
+        let config = MutationConfig::load(vec![MutationRule::Numeric], None).unwrap();
+        assert_eq!(
+            config.numeric_suffixes,
+            DEFAULT_NUMERIC_SUFFIXES
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn load_from_rule_file_picks_up_custom_suffixes_and_tags() {
+        let path = temp_rule_file(
+            r#"
+            numeric_suffixes = ["9", "99"]
+            env_tags = ["qa"]
+            "#,
+        );
+        let config = MutationConfig::load(vec![MutationRule::Numeric], Some(&path)).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(
+            config.numeric_suffixes,
+            vec!["9".to_string(), "99".to_string()]
+        );
+        assert_eq!(config.env_tags, vec!["qa".to_string()]);
+    }
+
+    #[test]
+    fn load_honors_explicit_empty_numeric_suffixes_override() {
+        let path = temp_rule_file("numeric_suffixes = []\n");
+        let config = MutationConfig::load(vec![MutationRule::Numeric], Some(&path)).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(config.numeric_suffixes.is_empty());
+        // env_tags was omitted entirely, so it still falls back to the defaults.
+        assert_eq!(
+            config.env_tags,
+            DEFAULT_ENV_TAGS
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn config_with(rules: Vec<MutationRule>) -> MutationConfig {
+        MutationConfig {
+            rules,
+            numeric_suffixes: DEFAULT_NUMERIC_SUFFIXES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            env_tags: DEFAULT_ENV_TAGS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn expand_without_rules_returns_original_wordlist() {
+        let words = vec!["www".to_string(), "api".to_string()];
+        let config = config_with(vec![]);
+        let mut result = expand(&words, &config);
+        result.sort();
+        assert_eq!(result, vec!["api".to_string(), "www".to_string()]);
+    }
+
+    #[test]
+    fn expand_with_empty_wordlist_returns_empty() {
+        let words: Vec<String> = vec![];
+        let config = config_with(vec![MutationRule::Numeric, MutationRule::Concat]);
+        assert!(expand(&words, &config).is_empty());
+    }
+
+    #[test]
+    fn expand_numeric_rule_adds_prefixed_and_suffixed_variants() {
+        let words = vec!["www".to_string()];
+        let config = config_with(vec![MutationRule::Numeric]);
+        let result = expand(&words, &config);
+        assert!(result.contains(&"www1".to_string()));
+        assert!(result.contains(&"www01".to_string()));
+        assert!(result.contains(&"1www".to_string()));
+    }
+
+    #[test]
+    fn expand_env_rule_adds_tagged_variants() {
+        let words = vec!["www".to_string()];
+        let config = config_with(vec![MutationRule::Env]);
+        let result = expand(&words, &config);
+        assert!(result.contains(&"www-dev".to_string()));
+        assert!(result.contains(&"www.staging".to_string()));
+    }
+
+    #[test]
+    fn expand_separators_rule_swaps_dot_and_dash() {
+        let words = vec!["api.v1".to_string(), "api-v2".to_string()];
+        let config = config_with(vec![MutationRule::Separators]);
+        let result = expand(&words, &config);
+        assert!(result.contains(&"api-v1".to_string()));
+        assert!(result.contains(&"api.v2".to_string()));
+    }
+
+    #[test]
+    fn expand_concat_rule_pairs_distinct_words_only() {
+        let words = vec!["www".to_string(), "api".to_string()];
+        let config = config_with(vec![MutationRule::Concat]);
+        let result = expand(&words, &config);
+        assert!(result.contains(&"wwwapi".to_string()));
+        assert!(result.contains(&"apiwww".to_string()));
+        assert!(!result.contains(&"wwwwww".to_string()));
+    }
+
+    #[test]
+    fn expand_dedups_across_words_and_rules() {
+        let words = vec!["www".to_string(), "www".to_string()];
+        let config = config_with(vec![MutationRule::Numeric]);
+        let result = expand(&words, &config);
+        assert_eq!(result.iter().filter(|w| *w == "www1").count(), 1);
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Prefix index over cached NSEC3 hashes, letting the zone-walk solver answer
+/// "which candidate names have a hash starting with these N characters"
+/// instead of failing an exact lookup when a captured hash is truncated.
+///
+/// Hashes are base32 (RFC4648), so a sorted map already groups shared
+/// prefixes together; a prefix query is a bounded range scan from the
+/// prefix's lower bound, which gives radix-tree lookup semantics without a
+/// node-pointer structure to build and serialize.
+#[derive(Serialize, Deserialize, Default)]
+pub struct PrefixIndex {
+    entries: BTreeMap<String, String>,
+    /// Order-independent fingerprint of the source cache's hash map, used to
+    /// detect a persisted index that no longer matches its cache (e.g. the
+    /// cache was regenerated from a larger or mutated wordlist).
+    fingerprint: u64,
+}
+
+/// FNV-1a over a string, used to build an order-independent fingerprint of
+/// a hash map without pulling in a checksum crate.
+fn fnv1a(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in s.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Compute a fingerprint of a cache's hash map that is independent of
+/// iteration order, so it can be compared against a value stored in a
+/// persisted index built from the same map.
+fn fingerprint_of(hashes: &HashMap<String, String>) -> u64 {
+    hashes
+        .iter()
+        .fold(hashes.len() as u64, |acc, (hash, fqdn)| {
+            acc ^ fnv1a(hash).wrapping_add(fnv1a(fqdn))
+        })
+}
+
+impl PrefixIndex {
+    /// Build an index from a cache's hash -> FQDN map.
+    pub fn build(hashes: &HashMap<String, String>) -> Self {
+        Self {
+            entries: hashes.iter().map(|(h, f)| (h.clone(), f.clone())).collect(),
+            fingerprint: fingerprint_of(hashes),
+        }
+    }
+
+    /// Whether this index was built from exactly `hashes` (same keys and
+    /// values), and can therefore be trusted instead of rebuilt.
+    pub fn matches(&self, hashes: &HashMap<String, String>) -> bool {
+        self.fingerprint == fingerprint_of(hashes)
+    }
+
+    /// Return all (hash, fqdn) pairs whose hash starts with `prefix`.
+    pub fn lookup_prefix(&self, prefix: &str) -> Vec<(String, String)> {
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+
+        self.entries
+            .range(prefix.to_string()..)
+            .take_while(|(hash, _)| hash.starts_with(prefix))
+            .map(|(hash, fqdn)| (hash.clone(), fqdn.clone()))
+            .collect()
+    }
+
+    /// Load a previously persisted index, if present.
+    pub fn load(path: &Path) -> std::io::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data).ok())
+    }
+
+    /// Persist the index alongside the JSON cache so repeated solver runs
+    /// don't have to rebuild it from the cache's hash map.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+    }
+}
+
+/// Derive the index filename that accompanies a given cache filename
+/// (`nsec3_<hash>.json` -> `nsec3_<hash>.idx.json`).
+pub fn index_filename(cache_filename: &str) -> String {
+    match cache_filename.strip_suffix(".json") {
+        Some(stem) => format!("{stem}.idx.json"),
+        None => format!("{cache_filename}.idx.json"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_hashes() -> HashMap<String, String> {
+        HashMap::from([
+            ("aaaaaa".to_string(), "one.example.com".to_string()),
+            ("aaaabb".to_string(), "two.example.com".to_string()),
+            ("bbbbbb".to_string(), "three.example.com".to_string()),
+        ])
+    }
+
+    #[test]
+    fn lookup_prefix_empty_prefix_returns_nothing() {
+        let index = PrefixIndex::build(&sample_hashes());
+        assert!(index.lookup_prefix("").is_empty());
+    }
+
+    #[test]
+    fn lookup_prefix_exact_boundary_match() {
+        let index = PrefixIndex::build(&sample_hashes());
+        let matches = index.lookup_prefix("bbbbbb");
+        assert_eq!(
+            matches,
+            vec![("bbbbbb".to_string(), "three.example.com".to_string())]
+        );
+    }
+
+    #[test]
+    fn lookup_prefix_ambiguous_prefix_returns_all_candidates() {
+        let index = PrefixIndex::build(&sample_hashes());
+        let mut matches = index.lookup_prefix("aaaa");
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![
+                ("aaaaaa".to_string(), "one.example.com".to_string()),
+                ("aaaabb".to_string(), "two.example.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_prefix_no_match() {
+        let index = PrefixIndex::build(&sample_hashes());
+        assert!(index.lookup_prefix("zzzz").is_empty());
+    }
+
+    #[test]
+    fn matches_detects_unchanged_and_changed_cache() {
+        let hashes = sample_hashes();
+        let index = PrefixIndex::build(&hashes);
+        assert!(index.matches(&hashes));
+
+        let mut mutated = hashes.clone();
+        mutated.insert("cccccc".to_string(), "four.example.com".to_string());
+        assert!(!index.matches(&mutated));
+    }
+}
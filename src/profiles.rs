@@ -0,0 +1,187 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// One named NSEC3 configuration from a profiles file.
+#[derive(Deserialize)]
+struct Profile {
+    name: String,
+    #[serde(default)]
+    salt: String,
+    #[serde(default)]
+    iterations: u32,
+}
+
+#[derive(Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profile: Vec<Profile>,
+}
+
+/// The configurations `generate_common_configs` runs when no `--profiles`
+/// file is given: real-world salt/iteration combinations seen in the wild.
+pub(crate) fn default_configs() -> Vec<(String, String, u32)> {
+    vec![
+        (
+            "No salt, no iterations (30% of NSEC3 domains)".to_string(),
+            String::new(),
+            0,
+        ),
+        ("Google Cloud DNS".to_string(), "DEADBEEF".to_string(), 5),
+        ("AWS Route53".to_string(), "CAFEBABE".to_string(), 10),
+        ("Cloudflare minimal".to_string(), "00".to_string(), 0),
+        ("Light security".to_string(), "AABBCCDD".to_string(), 3),
+        ("Medium security".to_string(), "12345678".to_string(), 5),
+        ("High security".to_string(), "FEDCBA98".to_string(), 10),
+        ("Very high security".to_string(), "FFFFFFFF".to_string(), 15),
+    ]
+}
+
+/// RFC 5155 caps NSEC3 iterations well below `u32::MAX`; reject anything
+/// past that as a typo'd config rather than silently generating it.
+const MAX_ITERATIONS: u32 = 2500;
+
+/// Load and validate a user-supplied profiles file into the same
+/// `(name, salt, iterations)` shape the generation loop already consumes.
+/// Accepts both `.toml` and `.json` profile files.
+pub(crate) fn load_profiles(
+    path: &PathBuf,
+) -> Result<Vec<(String, String, u32)>, Box<dyn std::error::Error>> {
+    let data = std::fs::read_to_string(path)?;
+
+    let parsed: ProfilesFile = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&data)?,
+        Some("json") => serde_json::from_str(&data)?,
+        _ => {
+            return Err(format!(
+                "unsupported profiles file format for {} (expected .toml or .json)",
+                path.display()
+            )
+            .into())
+        }
+    };
+
+    if parsed.profile.is_empty() {
+        return Err(format!("no profile entries found in {}", path.display()).into());
+    }
+
+    let mut configs = Vec::with_capacity(parsed.profile.len());
+    for profile in parsed.profile {
+        if !profile.salt.is_empty() && hex::decode(&profile.salt).is_err() {
+            return Err(format!(
+                "profile \"{}\": salt \"{}\" is not valid hex",
+                profile.name, profile.salt
+            )
+            .into());
+        }
+        if profile.iterations > MAX_ITERATIONS {
+            return Err(format!(
+                "profile \"{}\": iterations {} exceeds the maximum of {}",
+                profile.name, profile.iterations, MAX_ITERATIONS
+            )
+            .into());
+        }
+        configs.push((profile.name, profile.salt, profile.iterations));
+    }
+
+    Ok(configs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_FILE_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_profiles_file(extension: &str, contents: &str) -> PathBuf {
+        let id = TEST_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "nsecgen_profiles_test_{}_{}.{}",
+            std::process::id(),
+            id,
+            extension
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_valid_toml_profile() {
+        let path = temp_profiles_file(
+            "toml",
+            r#"
+            [[profile]]
+            name = "custom"
+            salt = "AABBCCDD"
+            iterations = 5
+            "#,
+        );
+        let configs = load_profiles(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            configs,
+            vec![("custom".to_string(), "AABBCCDD".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn loads_valid_json_profile() {
+        let path = temp_profiles_file(
+            "json",
+            r#"{"profile": [{"name": "custom", "salt": "AABBCCDD", "iterations": 5}]}"#,
+        );
+        let configs = load_profiles(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            configs,
+            vec![("custom".to_string(), "AABBCCDD".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_hex_salt() {
+        let path = temp_profiles_file(
+            "toml",
+            r#"
+            [[profile]]
+            name = "bad-salt"
+            salt = "not-hex"
+            iterations = 5
+            "#,
+        );
+        let err = load_profiles(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert!(err.to_string().contains("not valid hex"));
+    }
+
+    #[test]
+    fn rejects_iterations_over_max() {
+        let path = temp_profiles_file(
+            "toml",
+            r#"
+            [[profile]]
+            name = "too-many-iterations"
+            iterations = 999999
+            "#,
+        );
+        let err = load_profiles(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert!(err.to_string().contains("exceeds the maximum"));
+    }
+
+    #[test]
+    fn rejects_unsupported_extension() {
+        let path = temp_profiles_file("yaml", "profile: []");
+        let err = load_profiles(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert!(err.to_string().contains("unsupported profiles file format"));
+    }
+
+    #[test]
+    fn rejects_empty_profile_list() {
+        let path = temp_profiles_file("toml", "");
+        let err = load_profiles(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+        assert!(err.to_string().contains("no profile entries found"));
+    }
+}
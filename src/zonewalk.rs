@@ -0,0 +1,220 @@
+use crate::cache;
+use crate::prefixtree::{self, PrefixIndex};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+/// Parse a captured NSEC3 record file into the set of observed owner hashes.
+///
+/// Accepts either raw base32 hashes (one per line) or full `dig`-style NSEC3
+/// RR text, e.g. `2vptu5timamqttgl4luu9kg21e0aor3s.example.com. 3600 IN NSEC3
+/// 1 0 10 AABBCCDD (...)`, in which case only the owner name's hash label is
+/// kept.
+fn parse_observed_hashes(path: &PathBuf) -> std::io::Result<HashSet<String>> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut hashes = HashSet::new();
+
+    for line in reader.lines().map_while(Result::ok) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+
+        let owner = line.split_whitespace().next().unwrap_or(line);
+        let label = owner
+            .trim_end_matches('.')
+            .split('.')
+            .next()
+            .unwrap_or(owner);
+
+        hashes.insert(label.to_lowercase());
+    }
+
+    Ok(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_FILE_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn temp_records_file(contents: &str) -> PathBuf {
+        let id = TEST_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "nsecgen_zonewalk_test_{}_{}.txt",
+            std::process::id(),
+            id
+        ));
+        let mut file = File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_raw_hash_line() {
+        let path = temp_records_file("2vptu5timamqttgl4luu9kg21e0aor3s\n");
+        let hashes = parse_observed_hashes(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            hashes,
+            HashSet::from(["2vptu5timamqttgl4luu9kg21e0aor3s".to_string()])
+        );
+    }
+
+    #[test]
+    fn parses_full_dig_style_rr_line() {
+        let path = temp_records_file(
+            "2vptu5timamqttgl4luu9kg21e0aor3s.example.com. 3600 IN NSEC3 1 0 10 AABBCCDD (\n",
+        );
+        let hashes = parse_observed_hashes(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            hashes,
+            HashSet::from(["2vptu5timamqttgl4luu9kg21e0aor3s".to_string()])
+        );
+    }
+
+    #[test]
+    fn parses_mixed_file_with_comments_and_blank_lines() {
+        let path = temp_records_file(concat!(
+            "; zone transfer capture\n",
+            "\n",
+            "   \n",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.example.com. 3600 IN NSEC3 1 0 10 - (\n",
+            "; another comment\n",
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n",
+        ));
+        let hashes = parse_observed_hashes(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            hashes,
+            HashSet::from([
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn ignores_comment_and_blank_only_file() {
+        let path = temp_records_file("; nothing here\n\n   \n");
+        let hashes = parse_observed_hashes(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(hashes.is_empty());
+    }
+
+    #[test]
+    fn parses_tab_separated_dig_line() {
+        let path = temp_records_file(
+            "cccccccccccccccccccccccccccccccc.example.com.\t3600\tIN\tNSEC3\t1 0 10 AABBCCDD (\n",
+        );
+        let hashes = parse_observed_hashes(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            hashes,
+            HashSet::from(["cccccccccccccccccccccccccccccccc".to_string()])
+        );
+    }
+
+    #[test]
+    fn bare_multi_label_hash_without_trailing_dot_keeps_first_label() {
+        let path = temp_records_file("dddddddddddddddddddddddddddddddd.example.com\n");
+        let hashes = parse_observed_hashes(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            hashes,
+            HashSet::from(["dddddddddddddddddddddddddddddddd".to_string()])
+        );
+    }
+}
+
+/// Reverse-resolve a set of captured NSEC3 hashes against the precomputed
+/// cache that matches the observed salt/iterations.
+pub fn run(
+    records: &PathBuf,
+    salt: &str,
+    iterations: u32,
+    output_dir: &PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cache_filename = cache::get_cache_filename(salt, iterations);
+
+    println!("🔎 NSEC3 zone-walk solver");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(
+        "   Cache:   {}/{}[.gz|.zst]",
+        output_dir.display(),
+        cache_filename
+    );
+    println!("   Records: {}", records.display());
+    println!();
+
+    let cache = cache::load_cache_file(output_dir, &cache_filename)?;
+
+    let index_path = output_dir.join(prefixtree::index_filename(&cache_filename));
+    let index = match PrefixIndex::load(&index_path)? {
+        Some(index) if index.matches(&cache.hashes) => index,
+        _ => {
+            let index = PrefixIndex::build(&cache.hashes);
+            index.save(&index_path)?;
+            index
+        }
+    };
+
+    let observed = parse_observed_hashes(records)?;
+
+    let mut resolved: Vec<(String, String)> = Vec::new();
+    let mut unresolved: Vec<&str> = Vec::new();
+
+    for hash in &observed {
+        if let Some(fqdn) = cache.hashes.get(hash) {
+            resolved.push((hash.clone(), fqdn.clone()));
+            continue;
+        }
+
+        // Possibly a truncated/partial hash captured from a log: fall back
+        // to a prefix match against the full cached hash set.
+        let candidates = index.lookup_prefix(hash);
+        match candidates.len() {
+            0 => unresolved.push(hash.as_str()),
+            1 => resolved.push(candidates.into_iter().next().unwrap()),
+            n => {
+                println!("⚠️  Ambiguous prefix {} matches {} candidates:", hash, n);
+                for (full_hash, fqdn) in &candidates {
+                    println!("      {} -> {}", full_hash, fqdn);
+                }
+                unresolved.push(hash.as_str());
+            }
+        }
+    }
+    resolved.sort_by(|a, b| a.1.cmp(&b.1));
+    unresolved.sort_unstable();
+
+    println!("📜 Recovered names:");
+    if resolved.is_empty() {
+        println!("   (none)");
+    }
+    for (hash, fqdn) in &resolved {
+        println!("   {} -> {}", hash, fqdn);
+    }
+    println!();
+
+    println!("📊 Coverage report:");
+    println!("   Chain hashes observed: {}", observed.len());
+    println!("   Resolved:              {}", resolved.len());
+    println!("   Unresolved:            {}", unresolved.len());
+
+    if !unresolved.is_empty() {
+        println!();
+        println!("❓ Unresolved hashes (no single match in the wordlist cache):");
+        for hash in &unresolved {
+            println!("   {}", hash);
+        }
+    }
+
+    Ok(())
+}